@@ -1,4 +1,6 @@
 use chrono::DateTime;
+use std::collections::HashMap;
+use std::io::{BufRead, IsTerminal, Write};
 use std::str::FromStr;
 
 /// struct that represents a single log entry in CRI log format.
@@ -6,11 +8,13 @@ use std::str::FromStr;
 ///    2016-10-06T00:17:09.669794202Z stdout P log content 1
 //     2016-10-06T00:17:09.669794203Z stderr F log content 2
 //  See: https://github.com/kubernetes/kubernetes/blob/master/pkg/kubelet/kuberuntime/logs/logs.go#L128
+#[derive(Debug)]
 pub struct CriLog {
     timestamp: DateTime<chrono::offset::FixedOffset>,
     stream_type: StreamType,
     tag: String,
     log: String,
+    reassembled: bool,
 }
 
 impl CriLog {
@@ -38,27 +42,156 @@ impl CriLog {
     pub fn log(&self) -> &str {
         &self.log
     }
+
+    /// Get the partial/full marker carried by the log entry's tag field.
+    ///
+    /// kubelet writes `P` when it had to split an oversized line and `F`
+    /// for the terminating fragment (or a line that fit whole).
+    pub fn stream_tag(&self) -> Result<StreamTag, ParsingError> {
+        StreamTag::from_str(&self.tag)
+    }
+
+    /// Returns true if this entry was produced by [`LogReassembler`]
+    /// concatenating more than one fragment: a run of `P` entries with
+    /// their trailing `F` entry, or a run of two or more `P` entries left
+    /// unterminated at the end of the input.
+    pub fn reassembled(&self) -> bool {
+        self.reassembled
+    }
 }
 
 impl FromStr for CriLog {
     type Err = ParsingError;
 
     fn from_str(input: &str) -> Result<Self, <Self as FromStr>::Err> {
-        let mut iter = input.split_whitespace();
+        CriLogRef::try_from(input).map(|entry| entry.into_owned())
+    }
+}
+
+/// Borrowed counterpart of [`CriLog`] whose `tag` and `log` are slices
+/// into the input line rather than owned `String`s.
+///
+/// Parse with [`TryFrom`] and, when you need to keep the entry past the
+/// lifetime of the input, promote it with [`CriLogRef::into_owned`]. This
+/// avoids a per-line allocation for callers streaming millions of lines,
+/// and — unlike the historical `split_whitespace` parse — preserves the
+/// message body verbatim, including runs of spaces and tabs.
+#[derive(Debug)]
+pub struct CriLogRef<'a> {
+    timestamp: DateTime<chrono::offset::FixedOffset>,
+    stream_type: StreamType,
+    tag: &'a str,
+    log: &'a str,
+}
+
+impl<'a> CriLogRef<'a> {
+    /// Get timestamp associated to log entry
+    pub fn timestamp(&self) -> &DateTime<chrono::offset::FixedOffset> {
+        &self.timestamp
+    }
+
+    /// Returns true if log entry is of type stderr
+    pub fn is_stderr(&self) -> bool {
+        self.stream_type == StreamType::StdErr
+    }
 
-        let timestamp_str = iter.next().ok_or(ParsingError::MissingTimestamp)?;
-        let timestamp = DateTime::parse_from_rfc3339(timestamp_str)
-            .map_err(|_| ParsingError::TimestampFormat(timestamp_str.into()))?;
+    /// Returns true if log entry is of type stdout
+    pub fn is_stdout(&self) -> bool {
+        self.stream_type == StreamType::StdOut
+    }
 
-        let stream_type_str = iter.next().ok_or(ParsingError::MissingStreamType)?;
-        let stream_type = StreamType::from_str(stream_type_str)
-            .map_err(|_| ParsingError::InvalidStreamType(stream_type_str.into()))?;
+    /// Get tag attribute from log entry
+    pub fn tag(&self) -> &str {
+        self.tag
+    }
 
-        let tag = iter.next().ok_or(ParsingError::MissingLogTag)?.to_owned();
+    /// Get message from log entry
+    pub fn log(&self) -> &str {
+        self.log
+    }
 
-        let log = iter.collect::<Vec<&str>>().join(" ");
+    /// Get the partial/full marker carried by the log entry's tag field.
+    pub fn stream_tag(&self) -> Result<StreamTag, ParsingError> {
+        StreamTag::from_str(self.tag)
+    }
 
-        Ok(CriLog {
+    /// Copy this borrowed entry into an owned [`CriLog`].
+    pub fn into_owned(self) -> CriLog {
+        CriLog {
+            timestamp: self.timestamp,
+            stream_type: self.stream_type,
+            tag: self.tag.to_owned(),
+            log: self.log.to_owned(),
+            reassembled: false,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for CriLogRef<'a> {
+    type Error = ParsingError;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        // Split on the first three whitespace boundaries only, so the
+        // message tail is taken byte-for-byte instead of being
+        // re-joined with collapsed whitespace.
+        let mut iter = input.splitn(4, char::is_whitespace);
+
+        let timestamp_str = match iter.next().filter(|field| !field.is_empty()) {
+            Some(field) => field,
+            None => {
+                return Err(ParsingError::MissingTimestamp {
+                    line: input.to_owned(),
+                })
+            }
+        };
+        let timestamp = match DateTime::parse_from_rfc3339(timestamp_str) {
+            Ok(timestamp) => timestamp,
+            Err(source) => {
+                // Prefer a precise positional diagnostic; fall back to the
+                // underlying chrono error for out-of-range but well-formed
+                // timestamps.
+                return Err(match validate_rfc3339(timestamp_str) {
+                    Err((column, message)) => ParsingError::Timestamp {
+                        line: input.to_owned(),
+                        column,
+                        message,
+                    },
+                    Ok(()) => ParsingError::TimestampFormat {
+                        line: input.to_owned(),
+                        source,
+                    },
+                });
+            }
+        };
+
+        let stream_type_str = match iter.next() {
+            Some(field) => field,
+            None => {
+                return Err(ParsingError::MissingStreamType {
+                    line: input.to_owned(),
+                })
+            }
+        };
+        let stream_type = StreamType::from_str(stream_type_str).map_err(|_| {
+            ParsingError::InvalidStreamType {
+                line: input.to_owned(),
+                stream_type: stream_type_str.to_owned(),
+            }
+        })?;
+
+        let tag = match iter.next() {
+            Some(field) => field,
+            None => {
+                return Err(ParsingError::MissingLogTag {
+                    line: input.to_owned(),
+                })
+            }
+        };
+
+        // A line with nothing after the tag carries an empty message.
+        let log = iter.next().unwrap_or("");
+
+        Ok(CriLogRef {
             timestamp,
             stream_type,
             tag,
@@ -67,26 +200,565 @@ impl FromStr for CriLog {
     }
 }
 
+impl<'a> From<CriLogRef<'a>> for CriLog {
+    fn from(entry: CriLogRef<'a>) -> Self {
+        entry.into_owned()
+    }
+}
+
+/// Streaming parser over any [`BufRead`], yielding one parsed [`CriLog`]
+/// per line so callers can tail a rotating container log file without
+/// splitting the input themselves.
+///
+/// Two quirks of real runtime logs are handled: a trailing line with no
+/// newline terminator (a half-written entry the runtime is still
+/// flushing) is detected and dropped rather than reported as an error,
+/// and — unless [`CriLogReader::skip_malformed`] is set — a line that
+/// fails to parse is surfaced as an `Err` item while the stream keeps
+/// going to the next line.
+pub struct CriLogReader<R> {
+    reader: R,
+    skip_malformed: bool,
+}
+
+impl<R: BufRead> CriLogReader<R> {
+    /// Wrap a buffered reader. Malformed lines are yielded as `Err`.
+    pub fn new(reader: R) -> Self {
+        CriLogReader {
+            reader,
+            skip_malformed: false,
+        }
+    }
+
+    /// Silently drop lines that fail to parse instead of yielding them as
+    /// `Err`, so the iterator only ever produces successfully parsed
+    /// entries.
+    pub fn skip_malformed(mut self) -> Self {
+        self.skip_malformed = true;
+        self
+    }
+}
+
+impl<R: BufRead> Iterator for CriLogReader<R> {
+    type Item = Result<CriLog, ParsingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut line = String::new();
+            let read = match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(read) => read,
+                Err(err) => return Some(Err(ParsingError::Io(err))),
+            };
+
+            // A final chunk without a newline is an entry still being
+            // written by the runtime; skip it and stop the stream.
+            if !line.ends_with('\n') {
+                debug_assert!(read > 0);
+                return None;
+            }
+
+            // Strip only the line terminator (\n, and a preceding \r)
+            // so a payload ending in spaces or tabs survives the
+            // streaming path intact.
+            let line = line.strip_suffix('\n').unwrap_or(&line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if line.is_empty() {
+                continue;
+            }
+
+            match CriLog::from_str(line) {
+                Ok(log) => return Some(Ok(log)),
+                Err(err) if self.skip_malformed => {
+                    let _ = err;
+                    continue;
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// A single piece of a parsed [`OutputFormat`] template: either literal
+/// text or a placeholder for one of a [`CriLog`]'s fields.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FormatSegment {
+    Literal(String),
+    /// The entry timestamp, optionally with a `chrono` strftime sub-format.
+    Timestamp(Option<String>),
+    Stream,
+    Tag,
+    Log,
+}
+
+/// A re-usable output template compiled from a string such as
+/// `"{timestamp} [{stream}] {tag}: {log}"`.
+///
+/// The template is parsed once into a sequence of [`FormatSegment`]s and
+/// then applied to any number of entries with [`OutputFormat::render`].
+/// Recognised fields are `timestamp`, `stream`, `tag` and `log`; the
+/// timestamp accepts a `chrono` sub-format after a colon, e.g.
+/// `{timestamp:%H:%M:%S%.3f}`. Write a literal brace by doubling it
+/// (`{{` / `}}`).
+#[derive(Debug)]
+pub struct OutputFormat {
+    segments: Vec<FormatSegment>,
+}
+
+impl OutputFormat {
+    /// The compiled segments, in order.
+    pub fn segments(&self) -> &[FormatSegment] {
+        &self.segments
+    }
+
+    /// Render an entry by substituting each field placeholder.
+    pub fn render(&self, log: &CriLog) -> String {
+        use std::fmt::Write as _;
+
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                FormatSegment::Literal(text) => out.push_str(text),
+                FormatSegment::Timestamp(Some(fmt)) => {
+                    // The sub-format was trial-formatted at parse time, so
+                    // this write cannot fail; use the fallible path rather
+                    // than `to_string()` to avoid a panic on any spec that
+                    // slipped through.
+                    let _ = write!(out, "{}", log.timestamp.format(fmt));
+                }
+                FormatSegment::Timestamp(None) => out.push_str(&log.timestamp.to_rfc3339()),
+                FormatSegment::Stream => out.push_str(log.stream_type.as_str()),
+                FormatSegment::Tag => out.push_str(&log.tag),
+                FormatSegment::Log => out.push_str(&log.log),
+            }
+        }
+        out
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = ParsingError;
+
+    fn from_str(template: &str) -> Result<Self, <Self as FromStr>::Err> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    if !literal.is_empty() {
+                        segments.push(FormatSegment::Literal(std::mem::take(&mut literal)));
+                    }
+                    let mut field = String::new();
+                    let mut closed = false;
+                    for d in chars.by_ref() {
+                        if d == '}' {
+                            closed = true;
+                            break;
+                        }
+                        field.push(d);
+                    }
+                    if !closed {
+                        return Err(ParsingError::UnterminatedFormatField(field));
+                    }
+                    segments.push(parse_format_field(&field)?);
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(FormatSegment::Literal(literal));
+        }
+
+        Ok(OutputFormat { segments })
+    }
+}
+
+fn parse_format_field(field: &str) -> Result<FormatSegment, ParsingError> {
+    let (name, sub_format) = match field.split_once(':') {
+        Some((name, sub)) => (name, Some(sub.to_owned())),
+        None => (field, None),
+    };
+    match name {
+        "timestamp" => {
+            // Reject a bad strftime spec up front so a malformed template
+            // surfaces as a ParsingError instead of panicking later in
+            // `render`. An empty sub-format (`{timestamp:}`) is rejected
+            // too — it would otherwise render nothing. Trial-formatting a
+            // sentinel catches not only `Item::Error` but parse-only
+            // specifiers (e.g. `%#z`) that format into an `Err`.
+            if let Some(sub) = &sub_format {
+                if sub.is_empty() || !is_formattable_timestamp(sub) {
+                    return Err(ParsingError::InvalidTimestampFormat(sub.clone()));
+                }
+            }
+            Ok(FormatSegment::Timestamp(sub_format))
+        }
+        "stream" => Ok(FormatSegment::Stream),
+        "tag" => Ok(FormatSegment::Tag),
+        "log" => Ok(FormatSegment::Log),
+        other => Err(ParsingError::UnknownFormatField(other.to_owned())),
+    }
+}
+
+/// True if `fmt` is a strftime spec that can actually be *formatted* (not
+/// merely parsed): it is trial-formatted against a fixed sentinel, which
+/// rejects both invalid specifiers and parse-only ones such as `%#z` that
+/// would otherwise format into an error at render time.
+fn is_formattable_timestamp(fmt: &str) -> bool {
+    use std::fmt::Write as _;
+    let sentinel = DateTime::parse_from_rfc3339("2001-02-03T04:05:06.789Z")
+        .expect("sentinel timestamp is valid RFC3339");
+    let mut probe = String::new();
+    write!(probe, "{}", sentinel.format(fmt)).is_ok()
+}
+
+/// A strategy for re-emitting a [`CriLog`] in a structured line format a
+/// downstream log shipper can consume.
+///
+/// Implementors provide [`LogEncoder::encode_into`]; [`LogEncoder::encode`]
+/// is derived from it for the common in-memory case.
+pub trait LogEncoder {
+    /// Write the encoded representation of `log` to `writer`.
+    fn encode_into<W: Write>(&self, log: &CriLog, writer: &mut W) -> std::io::Result<()>;
+
+    /// Encode `log` into a freshly allocated `String`.
+    fn encode(&self, log: &CriLog) -> String {
+        let mut buffer = Vec::new();
+        self.encode_into(log, &mut buffer)
+            .expect("writing to a Vec is infallible");
+        String::from_utf8(buffer).expect("encoders only emit valid UTF-8")
+    }
+}
+
+/// Encodes entries as a single-line JSON object with `timestamp`,
+/// `stream`, `tag` and `log` keys.
+pub struct JsonEncoder;
+
+impl LogEncoder for JsonEncoder {
+    fn encode_into<W: Write>(&self, log: &CriLog, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(b"{\"timestamp\":\"")?;
+        writer.write_all(log.timestamp.to_rfc3339().as_bytes())?;
+        writer.write_all(b"\",\"stream\":\"")?;
+        writer.write_all(log.stream_type.as_str().as_bytes())?;
+        writer.write_all(b"\",\"tag\":\"")?;
+        write_json_escaped(writer, &log.tag)?;
+        writer.write_all(b"\",\"log\":\"")?;
+        write_json_escaped(writer, &log.log)?;
+        writer.write_all(b"\"}")?;
+        Ok(())
+    }
+}
+
+/// Encodes entries as logfmt: `time=... stream=... tag=... msg="..."`,
+/// with the message quoted and escaped.
+pub struct LogfmtEncoder;
+
+impl LogEncoder for LogfmtEncoder {
+    fn encode_into<W: Write>(&self, log: &CriLog, writer: &mut W) -> std::io::Result<()> {
+        write!(
+            writer,
+            "time={} stream={} tag={} msg=",
+            log.timestamp.to_rfc3339(),
+            log.stream_type.as_str(),
+            log.tag,
+        )?;
+        write_logfmt_quoted(writer, &log.log)
+    }
+}
+
+/// Escape a string for inclusion inside a JSON string literal.
+fn write_json_escaped<W: Write>(writer: &mut W, value: &str) -> std::io::Result<()> {
+    for c in value.chars() {
+        match c {
+            '"' => writer.write_all(b"\\\"")?,
+            '\\' => writer.write_all(b"\\\\")?,
+            '\n' => writer.write_all(b"\\n")?,
+            '\r' => writer.write_all(b"\\r")?,
+            '\t' => writer.write_all(b"\\t")?,
+            c if (c as u32) < 0x20 => write!(writer, "\\u{:04x}", c as u32)?,
+            c => write!(writer, "{}", c)?,
+        }
+    }
+    Ok(())
+}
+
+/// Write a double-quoted, escaped logfmt value.
+fn write_logfmt_quoted<W: Write>(writer: &mut W, value: &str) -> std::io::Result<()> {
+    writer.write_all(b"\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => writer.write_all(b"\\\"")?,
+            '\\' => writer.write_all(b"\\\\")?,
+            '\n' => writer.write_all(b"\\n")?,
+            '\r' => writer.write_all(b"\\r")?,
+            c => write!(writer, "{}", c)?,
+        }
+    }
+    writer.write_all(b"\"")?;
+    Ok(())
+}
+
+/// Predicate over parsed entries, built fluently, for grepping a
+/// container log by stream, tag and time window in a single pass.
+///
+/// Every configured criterion must hold for [`Filter::matches`] to
+/// return true; an unconfigured criterion matches everything. It
+/// composes with [`CriLogReader`] via `Iterator::filter`.
+#[derive(Debug, Default)]
+pub struct Filter {
+    stream: Option<StreamType>,
+    tag: Option<String>,
+    after: Option<DateTime<chrono::offset::FixedOffset>>,
+    before: Option<DateTime<chrono::offset::FixedOffset>>,
+}
+
+impl Filter {
+    /// A filter that matches every entry.
+    pub fn new() -> Self {
+        Filter::default()
+    }
+
+    /// Only match entries on the given stream.
+    pub fn stream(mut self, stream: StreamType) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Only match entries carrying the given tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Only match entries at or after the given instant.
+    pub fn after(mut self, timestamp: DateTime<chrono::offset::FixedOffset>) -> Self {
+        self.after = Some(timestamp);
+        self
+    }
+
+    /// Only match entries at or before the given instant.
+    pub fn before(mut self, timestamp: DateTime<chrono::offset::FixedOffset>) -> Self {
+        self.before = Some(timestamp);
+        self
+    }
+
+    /// Returns true when `log` satisfies every configured criterion.
+    pub fn matches(&self, log: &CriLog) -> bool {
+        if let Some(stream) = self.stream {
+            if log.stream_type != stream {
+                return false;
+            }
+        }
+        if let Some(tag) = &self.tag {
+            if log.tag != *tag {
+                return false;
+            }
+        }
+        if let Some(after) = self.after {
+            if log.timestamp < after {
+                return false;
+            }
+        }
+        if let Some(before) = self.before {
+            if log.timestamp > before {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Writes entries to a terminal with stderr lines highlighted in red and
+/// timestamps dimmed. Colorization auto-disables when the output is not a
+/// TTY (see [`ColorWriter::stdout`]) or can be forced off with
+/// [`ColorWriter::with_color`].
+pub struct ColorWriter<W> {
+    writer: W,
+    color: bool,
+}
+
+impl ColorWriter<std::io::Stdout> {
+    /// Write to standard output, colorizing only when stdout is a TTY.
+    pub fn stdout() -> Self {
+        let writer = std::io::stdout();
+        let color = writer.is_terminal();
+        ColorWriter { writer, color }
+    }
+}
+
+impl<W: Write> ColorWriter<W> {
+    /// Wrap an arbitrary writer, explicitly choosing whether to emit
+    /// ANSI color escapes.
+    pub fn with_color(writer: W, color: bool) -> Self {
+        ColorWriter { writer, color }
+    }
+
+    /// Render a single entry followed by a newline.
+    pub fn write(&mut self, log: &CriLog) -> std::io::Result<()> {
+        const DIM: &str = "\x1b[2m";
+        const RED: &str = "\x1b[31m";
+        const RESET: &str = "\x1b[0m";
+
+        let (dim, reset) = if self.color { (DIM, RESET) } else { ("", "") };
+        let (body, body_reset) = if self.color && log.is_stderr() {
+            (RED, RESET)
+        } else {
+            ("", "")
+        };
+
+        writeln!(
+            self.writer,
+            "{dim}{timestamp}{reset} {body}{stream} {tag} {log}{body_reset}",
+            timestamp = log.timestamp.to_rfc3339(),
+            stream = log.stream_type.as_str(),
+            tag = log.tag,
+            log = log.log,
+        )
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ParsingError {
-    #[error("Missing timestamp in log entry")]
-    MissingTimestamp,
-    #[error("Timestamp format error: {0}")]
-    TimestampFormat(String),
-    #[error("Missing stream type")]
-    MissingStreamType,
-    #[error("Invalid stream type: {0}")]
-    InvalidStreamType(String),
-    #[error("Missing log tag")]
-    MissingLogTag,
-}
-
-#[derive(Debug, PartialEq)]
+    #[error("Missing timestamp in log entry: {line:?}")]
+    MissingTimestamp { line: String },
+    #[error("Timestamp format error in log entry {line:?}: {source}")]
+    TimestampFormat {
+        line: String,
+        #[source]
+        source: chrono::ParseError,
+    },
+    #[error("Invalid timestamp: {message} at column {column} in log entry: {line:?}")]
+    Timestamp {
+        line: String,
+        column: usize,
+        message: String,
+    },
+    #[error("Missing stream type in log entry: {line:?}")]
+    MissingStreamType { line: String },
+    #[error("Invalid stream type {stream_type:?} in log entry: {line:?}")]
+    InvalidStreamType { line: String, stream_type: String },
+    #[error("Missing log tag in log entry: {line:?}")]
+    MissingLogTag { line: String },
+    #[error("Invalid stream tag: {0}")]
+    InvalidStreamTag(String),
+    #[error("I/O error while reading log: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Unknown field in output format: {0}")]
+    UnknownFormatField(String),
+    #[error("Unterminated field in output format: {0}")]
+    UnterminatedFormatField(String),
+    #[error("Invalid timestamp sub-format in output format: {0}")]
+    InvalidTimestampFormat(String),
+}
+
+/// Hand-rolled recursive-descent RFC3339 scanner used to pinpoint *where*
+/// a timestamp went wrong once `chrono` has rejected it. On failure it
+/// returns the 1-based column and a human description (e.g. "expected
+/// timezone offset"); a syntactically valid but semantically impossible
+/// timestamp (month 13, say) passes here and is reported through the
+/// wrapped [`chrono::ParseError`] instead.
+fn validate_rfc3339(input: &str) -> Result<(), (usize, String)> {
+    let bytes = input.as_bytes();
+    let mut pos = 0;
+
+    fn digits(bytes: &[u8], pos: &mut usize, count: usize, what: &str) -> Result<(), (usize, String)> {
+        for _ in 0..count {
+            match bytes.get(*pos) {
+                Some(b) if b.is_ascii_digit() => *pos += 1,
+                _ => return Err((*pos + 1, format!("expected {what}"))),
+            }
+        }
+        Ok(())
+    }
+
+    fn literal(bytes: &[u8], pos: &mut usize, expected: u8, what: &str) -> Result<(), (usize, String)> {
+        match bytes.get(*pos) {
+            Some(&b) if b == expected => {
+                *pos += 1;
+                Ok(())
+            }
+            _ => Err((*pos + 1, format!("expected {what}"))),
+        }
+    }
+
+    digits(bytes, &mut pos, 4, "4-digit year")?;
+    literal(bytes, &mut pos, b'-', "'-' after year")?;
+    digits(bytes, &mut pos, 2, "2-digit month")?;
+    literal(bytes, &mut pos, b'-', "'-' after month")?;
+    digits(bytes, &mut pos, 2, "2-digit day")?;
+
+    match bytes.get(pos) {
+        Some(b'T') | Some(b't') => pos += 1,
+        _ => return Err((pos + 1, "expected 'T' date/time separator".to_owned())),
+    }
+
+    digits(bytes, &mut pos, 2, "2-digit hour")?;
+    literal(bytes, &mut pos, b':', "':' after hour")?;
+    digits(bytes, &mut pos, 2, "2-digit minute")?;
+    literal(bytes, &mut pos, b':', "':' after minute")?;
+    digits(bytes, &mut pos, 2, "2-digit second")?;
+
+    if bytes.get(pos) == Some(&b'.') {
+        pos += 1;
+        if !bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+            return Err((pos + 1, "expected digit in fractional seconds".to_owned()));
+        }
+        while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+            pos += 1;
+        }
+    }
+
+    match bytes.get(pos) {
+        Some(b'Z') | Some(b'z') => pos += 1,
+        Some(b'+') | Some(b'-') => {
+            pos += 1;
+            digits(bytes, &mut pos, 2, "2-digit hours in timezone offset")?;
+            literal(bytes, &mut pos, b':', "':' in timezone offset")?;
+            digits(bytes, &mut pos, 2, "2-digit minutes in timezone offset")?;
+        }
+        _ => return Err((pos + 1, "expected timezone offset".to_owned())),
+    }
+
+    if pos != bytes.len() {
+        return Err((pos + 1, "unexpected trailing characters".to_owned()));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum StreamType {
     StdOut,
     StdErr,
 }
 
+impl StreamType {
+    /// The canonical CRI spelling of this stream (`stdout` / `stderr`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamType::StdOut => "stdout",
+            StreamType::StdErr => "stderr",
+        }
+    }
+}
+
+impl std::fmt::Display for StreamType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl FromStr for StreamType {
     type Err = InvalidStreamType;
     fn from_str(input: &str) -> Result<Self, <Self as FromStr>::Err> {
@@ -100,6 +772,106 @@ impl FromStr for StreamType {
 
 pub struct InvalidStreamType(String);
 
+/// The partial/full marker carried in a CRI log entry's tag field.
+///
+/// kubelet splits a log line that exceeds its read buffer into several
+/// `P` ("partial") fragments followed by a single `F` ("full") fragment;
+/// reassembling them reconstructs the message the container actually wrote.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StreamTag {
+    Partial,
+    Full,
+}
+
+impl FromStr for StreamTag {
+    type Err = ParsingError;
+    fn from_str(input: &str) -> Result<Self, <Self as FromStr>::Err> {
+        match input {
+            "P" => Ok(StreamTag::Partial),
+            "F" => Ok(StreamTag::Full),
+            input => Err(ParsingError::InvalidStreamTag(input.into())),
+        }
+    }
+}
+
+/// Adapter over an iterator of [`CriLog`] that stitches split lines back
+/// together. Consecutive `Partial` entries are buffered until the matching
+/// `Full` entry arrives, at which point a single merged [`CriLog`] is
+/// yielded carrying the timestamp of the first fragment and the
+/// concatenated body. Because kubelet interleaves stdout and stderr, a
+/// separate buffer is kept per [`StreamType`]. A lone `Full` with no
+/// pending partials passes through unchanged.
+pub struct LogReassembler<I> {
+    inner: I,
+    buffers: HashMap<StreamType, CriLog>,
+}
+
+impl<I> LogReassembler<I>
+where
+    I: Iterator<Item = CriLog>,
+{
+    /// Wrap an iterator of parsed entries.
+    pub fn new(inner: I) -> Self {
+        LogReassembler {
+            inner,
+            buffers: HashMap::new(),
+        }
+    }
+}
+
+impl<I> Iterator for LogReassembler<I>
+where
+    I: Iterator<Item = CriLog>,
+{
+    type Item = CriLog;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = match self.inner.next() {
+                Some(entry) => entry,
+                // Input exhausted: a dangling partial run was never
+                // terminated, so flush whatever we have buffered in a
+                // stable order (stdout before stderr).
+                None => {
+                    for stream in [StreamType::StdOut, StreamType::StdErr] {
+                        if let Some(entry) = self.buffers.remove(&stream) {
+                            return Some(entry);
+                        }
+                    }
+                    return None;
+                }
+            };
+
+            match entry.stream_tag() {
+                Ok(StreamTag::Partial) => match self.buffers.get_mut(&entry.stream_type) {
+                    // A second or later fragment: record that this buffer
+                    // is the result of concatenating more than one entry.
+                    Some(buffer) => {
+                        buffer.log.push_str(&entry.log);
+                        buffer.reassembled = true;
+                    }
+                    None => {
+                        self.buffers.insert(entry.stream_type, entry);
+                    }
+                },
+                Ok(StreamTag::Full) => match self.buffers.remove(&entry.stream_type) {
+                    Some(mut buffer) => {
+                        buffer.log.push_str(&entry.log);
+                        buffer.tag = entry.tag;
+                        buffer.reassembled = true;
+                        return Some(buffer);
+                    }
+                    // Lone `F`: nothing pending, pass through untouched.
+                    None => return Some(entry),
+                },
+                // An unrecognised tag can't participate in reassembly;
+                // yield it as-is so callers still see the entry.
+                Err(_) => return Some(entry),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +894,278 @@ mod tests {
         assert_eq!(crilog.tag(), "F");
         assert_eq!(crilog.log(), "log content 2");
     }
+
+    #[test]
+    fn reassembles_partial_run() {
+        let lines = [
+            "2016-10-06T00:17:09.669794202Z stdout P hello bea",
+            "2016-10-06T00:17:09.669794203Z stdout P utiful wo",
+            "2016-10-06T00:17:09.669794204Z stdout F rld",
+        ];
+        let logs = lines.iter().map(|l| CriLog::from_str(l).unwrap());
+        let merged: Vec<CriLog> = LogReassembler::new(logs).collect();
+        assert_eq!(merged.len(), 1);
+        assert!(merged[0].reassembled());
+        assert_eq!(merged[0].log(), "hello beautiful world");
+        assert_eq!(
+            merged[0].timestamp(),
+            &DateTime::parse_from_rfc3339("2016-10-06T00:17:09.669794202Z").unwrap()
+        );
+    }
+
+    #[test]
+    fn dangling_partials_flush_in_stable_order() {
+        // No terminating `F`: both streams have unterminated partials,
+        // stdout with two fragments and stderr with one.
+        let lines = [
+            "2016-10-06T00:17:09.669794202Z stdout P out-a o",
+            "2016-10-06T00:17:09.669794203Z stderr P err-a",
+            "2016-10-06T00:17:09.669794204Z stdout P ut-b",
+        ];
+        let logs = lines.iter().map(|l| CriLog::from_str(l).unwrap());
+        let flushed: Vec<CriLog> = LogReassembler::new(logs).collect();
+        assert_eq!(flushed.len(), 2);
+        // stdout is always flushed first.
+        assert!(flushed[0].is_stdout());
+        assert_eq!(flushed[0].log(), "out-a out-b");
+        assert!(flushed[0].reassembled());
+        // stderr had a single fragment, so it is not a reassembly.
+        assert!(flushed[1].is_stderr());
+        assert!(!flushed[1].reassembled());
+    }
+
+    #[test]
+    fn lone_full_passes_through() {
+        let log = CriLog::from_str("2016-10-06T00:17:09.669794202Z stdout F whole line").unwrap();
+        let merged: Vec<CriLog> = LogReassembler::new(std::iter::once(log)).collect();
+        assert_eq!(merged.len(), 1);
+        assert!(!merged[0].reassembled());
+        assert_eq!(merged[0].log(), "whole line");
+    }
+
+    #[test]
+    fn interleaved_streams_buffer_separately() {
+        let lines = [
+            "2016-10-06T00:17:09.669794202Z stdout P out-a o",
+            "2016-10-06T00:17:09.669794203Z stderr P err-a e",
+            "2016-10-06T00:17:09.669794204Z stdout F ut-b",
+            "2016-10-06T00:17:09.669794205Z stderr F rr-b",
+        ];
+        let logs = lines.iter().map(|l| CriLog::from_str(l).unwrap());
+        let merged: Vec<CriLog> = LogReassembler::new(logs).collect();
+        assert_eq!(merged.len(), 2);
+        assert!(merged[0].is_stdout());
+        assert_eq!(merged[0].log(), "out-a out-b");
+        assert!(merged[1].is_stderr());
+        assert_eq!(merged[1].log(), "err-a err-b");
+    }
+
+    #[test]
+    fn reader_parses_each_line() {
+        let input = "2016-10-06T00:17:09.669794202Z stdout F line one\n\
+                     2016-10-06T00:17:09.669794203Z stderr F line two\n";
+        let logs: Vec<CriLog> = CriLogReader::new(input.as_bytes())
+            .map(|r| r.expect("parse"))
+            .collect();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].log(), "line one");
+        assert_eq!(logs[1].log(), "line two");
+    }
+
+    #[test]
+    fn reader_skips_truncated_final_line() {
+        // The last line lacks a trailing newline: it is still being
+        // flushed and must be dropped, not parsed.
+        let input = "2016-10-06T00:17:09.669794202Z stdout F complete\n\
+                     2016-10-06T00:17:09.669794203Z stdout F half-writ";
+        let logs: Vec<CriLog> = CriLogReader::new(input.as_bytes())
+            .map(|r| r.expect("parse"))
+            .collect();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].log(), "complete");
+    }
+
+    #[test]
+    fn reader_preserves_trailing_message_whitespace() {
+        let input = "2016-10-06T00:17:09.669794202Z stdout F trailing spaces   \n";
+        let logs: Vec<CriLog> = CriLogReader::new(input.as_bytes())
+            .map(|r| r.expect("parse"))
+            .collect();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].log(), "trailing spaces   ");
+    }
+
+    #[test]
+    fn reader_continues_past_malformed_line() {
+        let input = "not a valid log line\n\
+                     2016-10-06T00:17:09.669794203Z stdout F good\n";
+        let results: Vec<Result<CriLog, ParsingError>> =
+            CriLogReader::new(input.as_bytes()).collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_err());
+        assert_eq!(results[1].as_ref().unwrap().log(), "good");
+
+        let good: Vec<CriLog> = CriLogReader::new(input.as_bytes())
+            .skip_malformed()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(good.len(), 1);
+        assert_eq!(good[0].log(), "good");
+    }
+
+    #[test]
+    fn output_format_renders_fields() {
+        let log =
+            CriLog::from_str("2016-10-06T00:17:09.669794202Z stderr F boom").expect("parse");
+        let format = OutputFormat::from_str("[{stream}] {tag}: {log}").expect("template");
+        assert_eq!(format.render(&log), "[stderr] F: boom");
+    }
+
+    #[test]
+    fn output_format_honors_timestamp_subformat() {
+        let log =
+            CriLog::from_str("2016-10-06T00:17:09.669794202Z stdout F hi").expect("parse");
+        let format = OutputFormat::from_str("{timestamp:%H:%M:%S%.3f} {log}").expect("template");
+        assert_eq!(format.render(&log), "00:17:09.669 hi");
+    }
+
+    #[test]
+    fn output_format_rejects_unknown_field() {
+        let err = OutputFormat::from_str("{bogus}").unwrap_err();
+        assert!(matches!(err, ParsingError::UnknownFormatField(f) if f == "bogus"));
+    }
+
+    #[test]
+    fn output_format_rejects_invalid_timestamp_subformat() {
+        let err = OutputFormat::from_str("{timestamp:%Q}").unwrap_err();
+        assert!(matches!(err, ParsingError::InvalidTimestampFormat(f) if f == "%Q"));
+    }
+
+    #[test]
+    fn output_format_rejects_parse_only_timestamp_subformat() {
+        // `%#z` parses but cannot be formatted; it must be rejected at
+        // parse time rather than panicking in render.
+        let err = OutputFormat::from_str("{timestamp:%#z}").unwrap_err();
+        assert!(matches!(err, ParsingError::InvalidTimestampFormat(f) if f == "%#z"));
+    }
+
+    #[test]
+    fn output_format_rejects_empty_timestamp_subformat() {
+        let err = OutputFormat::from_str("{timestamp:}").unwrap_err();
+        assert!(matches!(err, ParsingError::InvalidTimestampFormat(f) if f.is_empty()));
+    }
+
+    #[test]
+    fn json_encoder_emits_expected_keys() {
+        let log =
+            CriLog::from_str("2016-10-06T00:17:09.669794202Z stderr F boom").expect("parse");
+        assert_eq!(
+            JsonEncoder.encode(&log),
+            "{\"timestamp\":\"2016-10-06T00:17:09.669794202+00:00\",\
+             \"stream\":\"stderr\",\"tag\":\"F\",\"log\":\"boom\"}"
+        );
+    }
+
+    #[test]
+    fn json_encoder_escapes_message() {
+        let log =
+            CriLog::from_str("2016-10-06T00:17:09.669794202Z stdout F say \"hi\"").expect("parse");
+        assert!(JsonEncoder.encode(&log).ends_with("\"log\":\"say \\\"hi\\\"\"}"));
+    }
+
+    #[test]
+    fn preserves_message_whitespace() {
+        let log =
+            CriLog::from_str("2016-10-06T00:17:09.669794202Z stdout F a    b\tc").expect("parse");
+        assert_eq!(log.log(), "a    b\tc");
+    }
+
+    #[test]
+    fn borrowed_ref_slices_into_input() {
+        let line = "2016-10-06T00:17:09.669794202Z stdout F borrowed body";
+        let entry = CriLogRef::try_from(line).expect("parse");
+        assert!(entry.is_stdout());
+        assert_eq!(entry.tag(), "F");
+        // The log slice points back into the original input.
+        assert!(std::ptr::eq(
+            entry.log().as_ptr(),
+            line[line.len() - "borrowed body".len()..].as_ptr(),
+        ));
+        assert_eq!(entry.into_owned().log(), "borrowed body");
+    }
+
+    #[test]
+    fn filter_matches_stream_tag_and_window() {
+        let log =
+            CriLog::from_str("2016-10-06T00:17:09.669794202Z stderr F boom").expect("parse");
+        let after = DateTime::parse_from_rfc3339("2016-10-06T00:00:00Z").unwrap();
+        let before = DateTime::parse_from_rfc3339("2016-10-06T01:00:00Z").unwrap();
+
+        assert!(Filter::new()
+            .stream(StreamType::StdErr)
+            .tag("F")
+            .after(after)
+            .before(before)
+            .matches(&log));
+        assert!(!Filter::new().stream(StreamType::StdOut).matches(&log));
+        assert!(!Filter::new().tag("P").matches(&log));
+        assert!(!Filter::new().after(before).matches(&log));
+    }
+
+    #[test]
+    fn color_writer_plain_when_disabled() {
+        let log =
+            CriLog::from_str("2016-10-06T00:17:09.669794202Z stderr F boom").expect("parse");
+        let mut buffer = Vec::new();
+        ColorWriter::with_color(&mut buffer, false)
+            .write(&log)
+            .expect("write");
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "2016-10-06T00:17:09.669794202+00:00 stderr F boom\n"
+        );
+    }
+
+    #[test]
+    fn color_writer_highlights_stderr() {
+        let log =
+            CriLog::from_str("2016-10-06T00:17:09.669794202Z stderr F boom").expect("parse");
+        let mut buffer = Vec::new();
+        ColorWriter::with_color(&mut buffer, true)
+            .write(&log)
+            .expect("write");
+        let out = String::from_utf8(buffer).unwrap();
+        assert!(out.contains("\x1b[31m"));
+        assert!(out.contains("\x1b[2m"));
+    }
+
+    #[test]
+    fn timestamp_error_reports_column() {
+        let err = CriLog::from_str("2016-10-06T00:17:09 stdout F hi").unwrap_err();
+        match err {
+            ParsingError::Timestamp { column, message, .. } => {
+                assert_eq!(column, 20);
+                assert!(message.contains("timezone offset"));
+            }
+            other => panic!("expected positional timestamp error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn out_of_range_timestamp_chains_chrono_source() {
+        let err = CriLog::from_str("2016-13-06T00:17:09.6Z stdout F hi").unwrap_err();
+        assert!(matches!(err, ParsingError::TimestampFormat { .. }));
+        // The underlying chrono error is preserved as the error source.
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn logfmt_encoder_quotes_message() {
+        let log =
+            CriLog::from_str("2016-10-06T00:17:09.669794202Z stdout F hello world").expect("parse");
+        assert_eq!(
+            LogfmtEncoder.encode(&log),
+            "time=2016-10-06T00:17:09.669794202+00:00 stream=stdout tag=F msg=\"hello world\""
+        );
+    }
 }